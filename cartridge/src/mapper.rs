@@ -6,3 +6,255 @@ pub trait Mapper {
     // used for mapping internal changes, as cartridge is only ROM
     fn map_write(&self, address: u16, data: u8, device: Device);
 }
+
+/// The alphabet used to encode Game Genie codes, one nibble per letter.
+const GAME_GENIE_ALPHABET: &str = "APZLGITYEOXUKSVN";
+
+/// A single decoded Game Genie cheat: replace whatever is read at `address`
+/// with `value`, optionally only when the ROM byte still matches `compare`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cheat {
+    pub address: u16,
+    pub value: u8,
+    pub compare: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameGenieError {
+    InvalidLength,
+    InvalidLetter(char),
+}
+
+fn letter_to_nibble(c: char) -> Result<u8, GameGenieError> {
+    GAME_GENIE_ALPHABET
+        .find(c.to_ascii_uppercase())
+        .map(|i| i as u8)
+        .ok_or(GameGenieError::InvalidLetter(c))
+}
+
+impl Cheat {
+    /// Decodes a 6 or 8 letter Game Genie code into an address/value/compare
+    /// triple, following the NES Game Genie nibble scrambling scheme.
+    pub fn from_game_genie_code(code: &str) -> Result<Self, GameGenieError> {
+        let n: Vec<u8> = code
+            .chars()
+            .map(letter_to_nibble)
+            .collect::<Result<_, _>>()?;
+
+        match n.len() {
+            6 => {
+                let value = ((n[1] & 0x7) << 4) | ((n[0] & 0x8) << 4) | (n[0] & 0x7) | (n[5] & 0x8);
+                let address = 0x8000
+                    | ((n[3] as u16 & 0x7) << 12)
+                    | ((n[5] as u16 & 0x7) << 8)
+                    | ((n[4] as u16 & 0x8) << 8)
+                    | ((n[2] as u16 & 0x7) << 4)
+                    | ((n[1] as u16 & 0x8) << 4)
+                    | (n[4] as u16 & 0x7)
+                    | (n[3] as u16 & 0x8);
+
+                Ok(Cheat {
+                    address,
+                    value,
+                    compare: None,
+                })
+            }
+            8 => {
+                let value = ((n[1] & 0x7) << 4) | ((n[0] & 0x8) << 4) | (n[0] & 0x7) | (n[7] & 0x8);
+                let address = 0x8000
+                    | ((n[3] as u16 & 0x7) << 12)
+                    | ((n[5] as u16 & 0x7) << 8)
+                    | ((n[4] as u16 & 0x8) << 8)
+                    | ((n[2] as u16 & 0x7) << 4)
+                    | ((n[1] as u16 & 0x8) << 4)
+                    | (n[4] as u16 & 0x7)
+                    | (n[3] as u16 & 0x8);
+                let compare =
+                    ((n[7] & 0x7) << 4) | ((n[6] & 0x8) << 4) | (n[6] & 0x7) | (n[5] & 0x8);
+
+                Ok(Cheat {
+                    address,
+                    value,
+                    compare: Some(compare),
+                })
+            }
+            _ => Err(GameGenieError::InvalidLength),
+        }
+    }
+}
+
+/// A set of enabled Game Genie cheats, applied on top of CPU reads in the
+/// `$8000-$FFFF` range.
+#[derive(Debug, Clone, Default)]
+pub struct CheatList {
+    cheats: Vec<Cheat>,
+}
+
+impl CheatList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, cheat: Cheat) {
+        self.cheats.push(cheat);
+    }
+
+    pub fn remove(&mut self, address: u16) {
+        self.cheats.retain(|c| c.address != address);
+    }
+
+    pub fn cheats(&self) -> &[Cheat] {
+        &self.cheats
+    }
+
+    /// Given the address currently being read and the byte the ROM/mapper
+    /// would normally return, returns the cheat's replacement value if one
+    /// applies, otherwise the original byte unchanged.
+    pub fn apply_read(&self, address: u16, original: u8) -> u8 {
+        for cheat in &self.cheats {
+            if cheat.address != address {
+                continue;
+            }
+            match cheat.compare {
+                Some(compare) if compare != original => continue,
+                _ => return cheat.value,
+            }
+        }
+
+        original
+    }
+}
+
+/// Wraps any [`Mapper`] and applies an active [`CheatList`] on top of its
+/// reads, so an enabled Game Genie code actually changes what the CPU sees.
+pub struct CheatedMapper<M: Mapper> {
+    inner: M,
+    pub cheats: CheatList,
+}
+
+impl<M: Mapper> CheatedMapper<M> {
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            cheats: CheatList::new(),
+        }
+    }
+}
+
+impl<M: Mapper> Mapper for CheatedMapper<M> {
+    fn init(&mut self, pgr_count: u8, chr_count: u8) {
+        self.inner.init(pgr_count, chr_count);
+    }
+
+    fn map_read(&self, address: u16, device: Device) -> u16 {
+        let original = self.inner.map_read(address, device);
+        self.cheats.apply_read(address, original as u8) as u16
+    }
+
+    fn map_write(&self, address: u16, data: u8, device: Device) {
+        self.inner.map_write(address, data, device);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_six_letter_code_with_no_compare() {
+        let cheat = Cheat::from_game_genie_code("AAAAAA").unwrap();
+        assert_eq!(
+            cheat,
+            Cheat {
+                address: 0x8000,
+                value: 0,
+                compare: None,
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_six_letter_code_value_nibble() {
+        // 'P' is nibble 1 in GAME_GENIE_ALPHABET, and only contributes to
+        // the low bit of the replacement value
+        let cheat = Cheat::from_game_genie_code("PAAAAA").unwrap();
+        assert_eq!(cheat.value, 1);
+        assert_eq!(cheat.address, 0x8000);
+    }
+
+    #[test]
+    fn decodes_six_letter_code_address_nibble() {
+        // 'L' is nibble 3 in GAME_GENIE_ALPHABET, scrambled into bits 12-14
+        // of the address
+        let cheat = Cheat::from_game_genie_code("AAALAA").unwrap();
+        assert_eq!(cheat.address, 0xB000);
+    }
+
+    #[test]
+    fn decodes_eight_letter_code_with_compare() {
+        let cheat = Cheat::from_game_genie_code("AAAAAAAA").unwrap();
+        assert_eq!(
+            cheat,
+            Cheat {
+                address: 0x8000,
+                value: 0,
+                compare: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_length_code() {
+        assert_eq!(
+            Cheat::from_game_genie_code("AAAAA"),
+            Err(GameGenieError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_letter() {
+        assert_eq!(
+            Cheat::from_game_genie_code("AAAAAB"),
+            Err(GameGenieError::InvalidLetter('B'))
+        );
+    }
+
+    #[test]
+    fn cheat_list_applies_matching_cheat_without_compare() {
+        let mut cheats = CheatList::new();
+        cheats.add(Cheat {
+            address: 0x8000,
+            value: 0x42,
+            compare: None,
+        });
+
+        assert_eq!(cheats.apply_read(0x8000, 0x11), 0x42);
+        assert_eq!(cheats.apply_read(0x8001, 0x11), 0x11);
+    }
+
+    #[test]
+    fn cheat_list_respects_compare_byte() {
+        let mut cheats = CheatList::new();
+        cheats.add(Cheat {
+            address: 0x8000,
+            value: 0x42,
+            compare: Some(0x11),
+        });
+
+        assert_eq!(cheats.apply_read(0x8000, 0x11), 0x42);
+        assert_eq!(cheats.apply_read(0x8000, 0x99), 0x99);
+    }
+
+    #[test]
+    fn cheat_list_remove_stops_applying_it() {
+        let mut cheats = CheatList::new();
+        cheats.add(Cheat {
+            address: 0x8000,
+            value: 0x42,
+            compare: None,
+        });
+        cheats.remove(0x8000);
+
+        assert_eq!(cheats.apply_read(0x8000, 0x11), 0x11);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use plastic_core::nes_controller::StandardNESKey;
+use serde::{Deserialize, Serialize};
+
+/// One of the eight buttons on a standard NES controller, used as the key
+/// for keyboard/gamepad bindings in [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NesButton {
+    B,
+    A,
+    Select,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NesButton {
+    pub const ALL: [NesButton; 8] = [
+        NesButton::B,
+        NesButton::A,
+        NesButton::Select,
+        NesButton::Start,
+        NesButton::Up,
+        NesButton::Down,
+        NesButton::Left,
+        NesButton::Right,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            NesButton::B => "B",
+            NesButton::A => "A",
+            NesButton::Select => "Select",
+            NesButton::Start => "Start",
+            NesButton::Up => "Up",
+            NesButton::Down => "Down",
+            NesButton::Left => "Left",
+            NesButton::Right => "Right",
+        }
+    }
+
+    pub fn to_standard_key(self) -> StandardNESKey {
+        match self {
+            NesButton::B => StandardNESKey::B,
+            NesButton::A => StandardNESKey::A,
+            NesButton::Select => StandardNESKey::Select,
+            NesButton::Start => StandardNESKey::Start,
+            NesButton::Up => StandardNESKey::Up,
+            NesButton::Down => StandardNESKey::Down,
+            NesButton::Left => StandardNESKey::Left,
+            NesButton::Right => StandardNESKey::Right,
+        }
+    }
+
+    // bit position used to pack this button into a movie frame/gamepad state
+    pub fn bit(self) -> u16 {
+        1 << NesButton::ALL.iter().position(|&b| b == self).unwrap()
+    }
+}
+
+fn default_key_bindings() -> BTreeMap<String, String> {
+    [
+        (NesButton::B, "J"),
+        (NesButton::A, "K"),
+        (NesButton::Select, "U"),
+        (NesButton::Start, "I"),
+        (NesButton::Up, "W"),
+        (NesButton::Down, "S"),
+        (NesButton::Left, "A"),
+        (NesButton::Right, "D"),
+    ]
+    .into_iter()
+    .map(|(button, key)| (button.name().to_owned(), key.to_owned()))
+    .collect()
+}
+
+fn default_gamepad_bindings() -> BTreeMap<String, String> {
+    [
+        (NesButton::B, "East"),
+        (NesButton::A, "South"),
+        (NesButton::Select, "Select"),
+        (NesButton::Start, "Start"),
+        (NesButton::Up, "DPadUp"),
+        (NesButton::Down, "DPadDown"),
+        (NesButton::Left, "DPadLeft"),
+        (NesButton::Right, "DPadRight"),
+    ]
+    .into_iter()
+    .map(|(button, name)| (button.name().to_owned(), name.to_owned()))
+    .collect()
+}
+
+/// Persisted, user-editable settings: rebindable controls plus a handful of
+/// emulation defaults. Stored as `config.toml` in the platform data dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub key_bindings: BTreeMap<String, String>,
+    pub gamepad_bindings: BTreeMap<String, String>,
+    pub rewind_buffer_seconds: u64,
+    pub default_speed: f32,
+    pub audio_volume: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            key_bindings: default_key_bindings(),
+            gamepad_bindings: default_gamepad_bindings(),
+            rewind_buffer_seconds: 10,
+            default_speed: 1.0,
+            audio_volume: 1.0,
+        }
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    Some(crate::plastic_data_dir()?.join("config.toml"))
+}
+
+pub fn load() -> Config {
+    config_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(config: &Config) {
+    if let Some(path) = config_path() {
+        if let Ok(contents) = toml::to_string_pretty(config) {
+            _ = fs::write(path, contents);
+        }
+    }
+}
+
+/// Resolves the configured keyboard bindings into a lookup table, falling
+/// back to the default key for any button with a missing/invalid entry.
+pub fn resolve_key_bindings(config: &Config) -> Vec<(NesButton, egui::Key)> {
+    let defaults = default_key_bindings();
+
+    NesButton::ALL
+        .into_iter()
+        .map(|button| {
+            let key = config
+                .key_bindings
+                .get(button.name())
+                .and_then(|name| egui::Key::from_name(name))
+                .or_else(|| {
+                    defaults
+                        .get(button.name())
+                        .and_then(|name| egui::Key::from_name(name))
+                })
+                .unwrap_or(egui::Key::Escape);
+
+            (button, key)
+        })
+        .collect()
+}
+
+/// Resolves the configured gamepad bindings into a lookup table, falling
+/// back to the default button for any entry with a missing/invalid value.
+pub fn resolve_gamepad_bindings(config: &Config) -> Vec<(NesButton, gilrs::Button)> {
+    let defaults = default_gamepad_bindings();
+
+    NesButton::ALL
+        .into_iter()
+        .map(|button| {
+            let gamepad_button = config
+                .gamepad_bindings
+                .get(button.name())
+                .and_then(|name| parse_gamepad_button(name))
+                .or_else(|| {
+                    defaults
+                        .get(button.name())
+                        .and_then(|name| parse_gamepad_button(name))
+                })
+                .unwrap_or(gilrs::Button::Unknown);
+
+            (button, gamepad_button)
+        })
+        .collect()
+}
+
+fn parse_gamepad_button(name: &str) -> Option<gilrs::Button> {
+    use gilrs::Button::*;
+
+    Some(match name {
+        "South" => South,
+        "East" => East,
+        "North" => North,
+        "West" => West,
+        "C" => C,
+        "Z" => Z,
+        "LeftTrigger" => LeftTrigger,
+        "LeftTrigger2" => LeftTrigger2,
+        "RightTrigger" => RightTrigger,
+        "RightTrigger2" => RightTrigger2,
+        "Select" => Select,
+        "Start" => Start,
+        "Mode" => Mode,
+        "LeftThumb" => LeftThumb,
+        "RightThumb" => RightThumb,
+        "DPadUp" => DPadUp,
+        "DPadDown" => DPadDown,
+        "DPadLeft" => DPadLeft,
+        "DPadRight" => DPadRight,
+        _ => return None,
+    })
+}
@@ -0,0 +1,364 @@
+/// One memory-watch entry: an address and the format [`format_watch`]
+/// renders its value in. The debugger window lists these entries but does
+/// not read or display their values.
+pub struct MemoryWatch {
+    pub address: u16,
+    pub format: WatchFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchFormat {
+    Hex,
+    Decimal,
+    Signed,
+}
+
+impl WatchFormat {
+    fn format(self, value: u8) -> String {
+        match self {
+            WatchFormat::Hex => format!("${value:02X}"),
+            WatchFormat::Decimal => value.to_string(),
+            WatchFormat::Signed => (value as i8).to_string(),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            WatchFormat::Hex => "Hex",
+            WatchFormat::Decimal => "Decimal",
+            WatchFormat::Signed => "Signed",
+        }
+    }
+}
+
+// a 6502 instruction decoded for display; unofficial opcodes fall back to a
+// raw byte dump rather than a (possibly wrong) mnemonic
+struct Instruction {
+    mnemonic: &'static str,
+    mode: AddressingMode,
+}
+
+#[derive(Clone, Copy)]
+enum AddressingMode {
+    Implied,
+    Accumulator,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,
+    IndirectX,
+    IndirectY,
+    Relative,
+}
+
+impl AddressingMode {
+    fn operand_len(self) -> u16 {
+        match self {
+            AddressingMode::Implied | AddressingMode::Accumulator => 0,
+            AddressingMode::Immediate
+            | AddressingMode::ZeroPage
+            | AddressingMode::ZeroPageX
+            | AddressingMode::ZeroPageY
+            | AddressingMode::IndirectX
+            | AddressingMode::IndirectY
+            | AddressingMode::Relative => 1,
+            AddressingMode::Absolute
+            | AddressingMode::AbsoluteX
+            | AddressingMode::AbsoluteY
+            | AddressingMode::Indirect => 2,
+        }
+    }
+}
+
+fn decode(opcode: u8) -> Option<Instruction> {
+    use AddressingMode::*;
+
+    let (mnemonic, mode) = match opcode {
+        0xA9 => ("LDA", Immediate),
+        0xA5 => ("LDA", ZeroPage),
+        0xB5 => ("LDA", ZeroPageX),
+        0xAD => ("LDA", Absolute),
+        0xBD => ("LDA", AbsoluteX),
+        0xB9 => ("LDA", AbsoluteY),
+        0xA1 => ("LDA", IndirectX),
+        0xB1 => ("LDA", IndirectY),
+        0xA2 => ("LDX", Immediate),
+        0xA6 => ("LDX", ZeroPage),
+        0xB6 => ("LDX", ZeroPageY),
+        0xAE => ("LDX", Absolute),
+        0xBE => ("LDX", AbsoluteY),
+        0xA0 => ("LDY", Immediate),
+        0xA4 => ("LDY", ZeroPage),
+        0xB4 => ("LDY", ZeroPageX),
+        0xAC => ("LDY", Absolute),
+        0xBC => ("LDY", AbsoluteX),
+        0x85 => ("STA", ZeroPage),
+        0x95 => ("STA", ZeroPageX),
+        0x8D => ("STA", Absolute),
+        0x9D => ("STA", AbsoluteX),
+        0x99 => ("STA", AbsoluteY),
+        0x81 => ("STA", IndirectX),
+        0x91 => ("STA", IndirectY),
+        0x86 => ("STX", ZeroPage),
+        0x96 => ("STX", ZeroPageY),
+        0x8E => ("STX", Absolute),
+        0x84 => ("STY", ZeroPage),
+        0x94 => ("STY", ZeroPageX),
+        0x8C => ("STY", Absolute),
+        0xAA => ("TAX", Implied),
+        0x8A => ("TXA", Implied),
+        0xA8 => ("TAY", Implied),
+        0x98 => ("TYA", Implied),
+        0xBA => ("TSX", Implied),
+        0x9A => ("TXS", Implied),
+        0x48 => ("PHA", Implied),
+        0x68 => ("PLA", Implied),
+        0x08 => ("PHP", Implied),
+        0x28 => ("PLP", Implied),
+        0x69 => ("ADC", Immediate),
+        0x65 => ("ADC", ZeroPage),
+        0x75 => ("ADC", ZeroPageX),
+        0x6D => ("ADC", Absolute),
+        0x7D => ("ADC", AbsoluteX),
+        0x79 => ("ADC", AbsoluteY),
+        0x61 => ("ADC", IndirectX),
+        0x71 => ("ADC", IndirectY),
+        0xE9 => ("SBC", Immediate),
+        0xE5 => ("SBC", ZeroPage),
+        0xF5 => ("SBC", ZeroPageX),
+        0xED => ("SBC", Absolute),
+        0xFD => ("SBC", AbsoluteX),
+        0xF9 => ("SBC", AbsoluteY),
+        0xE1 => ("SBC", IndirectX),
+        0xF1 => ("SBC", IndirectY),
+        0x29 => ("AND", Immediate),
+        0x25 => ("AND", ZeroPage),
+        0x35 => ("AND", ZeroPageX),
+        0x2D => ("AND", Absolute),
+        0x3D => ("AND", AbsoluteX),
+        0x39 => ("AND", AbsoluteY),
+        0x21 => ("AND", IndirectX),
+        0x31 => ("AND", IndirectY),
+        0x09 => ("ORA", Immediate),
+        0x05 => ("ORA", ZeroPage),
+        0x15 => ("ORA", ZeroPageX),
+        0x0D => ("ORA", Absolute),
+        0x1D => ("ORA", AbsoluteX),
+        0x19 => ("ORA", AbsoluteY),
+        0x01 => ("ORA", IndirectX),
+        0x11 => ("ORA", IndirectY),
+        0x49 => ("EOR", Immediate),
+        0x45 => ("EOR", ZeroPage),
+        0x55 => ("EOR", ZeroPageX),
+        0x4D => ("EOR", Absolute),
+        0x5D => ("EOR", AbsoluteX),
+        0x59 => ("EOR", AbsoluteY),
+        0x41 => ("EOR", IndirectX),
+        0x51 => ("EOR", IndirectY),
+        0xC9 => ("CMP", Immediate),
+        0xC5 => ("CMP", ZeroPage),
+        0xD5 => ("CMP", ZeroPageX),
+        0xCD => ("CMP", Absolute),
+        0xDD => ("CMP", AbsoluteX),
+        0xD9 => ("CMP", AbsoluteY),
+        0xC1 => ("CMP", IndirectX),
+        0xD1 => ("CMP", IndirectY),
+        0xE0 => ("CPX", Immediate),
+        0xE4 => ("CPX", ZeroPage),
+        0xEC => ("CPX", Absolute),
+        0xC0 => ("CPY", Immediate),
+        0xC4 => ("CPY", ZeroPage),
+        0xCC => ("CPY", Absolute),
+        0xE6 => ("INC", ZeroPage),
+        0xF6 => ("INC", ZeroPageX),
+        0xEE => ("INC", Absolute),
+        0xFE => ("INC", AbsoluteX),
+        0xC6 => ("DEC", ZeroPage),
+        0xD6 => ("DEC", ZeroPageX),
+        0xCE => ("DEC", Absolute),
+        0xDE => ("DEC", AbsoluteX),
+        0xE8 => ("INX", Implied),
+        0xCA => ("DEX", Implied),
+        0xC8 => ("INY", Implied),
+        0x88 => ("DEY", Implied),
+        0x0A => ("ASL", Accumulator),
+        0x06 => ("ASL", ZeroPage),
+        0x16 => ("ASL", ZeroPageX),
+        0x0E => ("ASL", Absolute),
+        0x1E => ("ASL", AbsoluteX),
+        0x4A => ("LSR", Accumulator),
+        0x46 => ("LSR", ZeroPage),
+        0x56 => ("LSR", ZeroPageX),
+        0x4E => ("LSR", Absolute),
+        0x5E => ("LSR", AbsoluteX),
+        0x2A => ("ROL", Accumulator),
+        0x26 => ("ROL", ZeroPage),
+        0x36 => ("ROL", ZeroPageX),
+        0x2E => ("ROL", Absolute),
+        0x3E => ("ROL", AbsoluteX),
+        0x6A => ("ROR", Accumulator),
+        0x66 => ("ROR", ZeroPage),
+        0x76 => ("ROR", ZeroPageX),
+        0x6E => ("ROR", Absolute),
+        0x7E => ("ROR", AbsoluteX),
+        0x4C => ("JMP", Absolute),
+        0x6C => ("JMP", Indirect),
+        0x20 => ("JSR", Absolute),
+        0x60 => ("RTS", Implied),
+        0x40 => ("RTI", Implied),
+        0x90 => ("BCC", Relative),
+        0xB0 => ("BCS", Relative),
+        0xF0 => ("BEQ", Relative),
+        0xD0 => ("BNE", Relative),
+        0x10 => ("BPL", Relative),
+        0x30 => ("BMI", Relative),
+        0x50 => ("BVC", Relative),
+        0x70 => ("BVS", Relative),
+        0x24 => ("BIT", ZeroPage),
+        0x2C => ("BIT", Absolute),
+        0x18 => ("CLC", Implied),
+        0x38 => ("SEC", Implied),
+        0x58 => ("CLI", Implied),
+        0x78 => ("SEI", Implied),
+        0xB8 => ("CLV", Implied),
+        0xD8 => ("CLD", Implied),
+        0xF8 => ("SED", Implied),
+        0xEA => ("NOP", Implied),
+        0x00 => ("BRK", Implied),
+        _ => return None,
+    };
+
+    Some(Instruction { mnemonic, mode })
+}
+
+fn format_operand(mode: AddressingMode, bytes: &[u8]) -> String {
+    match mode {
+        AddressingMode::Implied => String::new(),
+        AddressingMode::Accumulator => "A".to_owned(),
+        AddressingMode::Immediate => format!("#${:02X}", bytes[0]),
+        AddressingMode::ZeroPage => format!("${:02X}", bytes[0]),
+        AddressingMode::ZeroPageX => format!("${:02X},X", bytes[0]),
+        AddressingMode::ZeroPageY => format!("${:02X},Y", bytes[0]),
+        AddressingMode::Absolute => format!("${:02X}{:02X}", bytes[1], bytes[0]),
+        AddressingMode::AbsoluteX => format!("${:02X}{:02X},X", bytes[1], bytes[0]),
+        AddressingMode::AbsoluteY => format!("${:02X}{:02X},Y", bytes[1], bytes[0]),
+        AddressingMode::Indirect => format!("(${:02X}{:02X})", bytes[1], bytes[0]),
+        AddressingMode::IndirectX => format!("(${:02X},X)", bytes[0]),
+        AddressingMode::IndirectY => format!("(${:02X}),Y", bytes[0]),
+        AddressingMode::Relative => format!("${:02X}", bytes[0]),
+    }
+}
+
+/// Disassembles `count` instructions starting at `start`, reading bytes via
+/// `peek`. Unknown/unofficial opcodes are rendered as a raw `.DB` byte.
+pub fn disassemble(start: u16, count: usize, peek: impl Fn(u16) -> u8) -> Vec<(u16, String)> {
+    let mut address = start;
+    let mut lines = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let opcode = peek(address);
+        let line_address = address;
+
+        match decode(opcode) {
+            Some(instruction) => {
+                let operand_len = instruction.mode.operand_len();
+                let operand_bytes: Vec<u8> = (1..=operand_len).map(|i| peek(address + i)).collect();
+                let operand = format_operand(instruction.mode, &operand_bytes);
+
+                lines.push((
+                    line_address,
+                    format!("{} {}", instruction.mnemonic, operand)
+                        .trim_end()
+                        .to_owned(),
+                ));
+                address += 1 + operand_len;
+            }
+            None => {
+                lines.push((line_address, format!(".DB ${opcode:02X}")));
+                address += 1;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Renders the watch value for an address, reading it fresh via `peek`.
+pub fn format_watch(watch: &MemoryWatch, peek: impl Fn(u16) -> u8) -> String {
+    watch.format.format(peek(watch.address))
+}
+
+pub const WATCH_FORMATS: [WatchFormat; 3] =
+    [WatchFormat::Hex, WatchFormat::Decimal, WatchFormat::Signed];
+
+pub fn watch_format_label(format: WatchFormat) -> &'static str {
+    format.label()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peek_from(bytes: &'static [u8]) -> impl Fn(u16) -> u8 {
+        move |addr| bytes.get(addr as usize).copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn disassembles_known_opcodes_with_operands() {
+        // LDA #$10; LDA $20; BRK
+        let lines = disassemble(0, 3, peek_from(&[0xA9, 0x10, 0xA5, 0x20, 0x00]));
+
+        assert_eq!(
+            lines,
+            vec![
+                (0, "LDA #$10".to_owned()),
+                (2, "LDA $20".to_owned()),
+                (4, "BRK".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_raw_byte_for_unofficial_opcode() {
+        let lines = disassemble(0, 1, peek_from(&[0xFF]));
+
+        assert_eq!(lines, vec![(0, ".DB $FF".to_owned())]);
+    }
+
+    #[test]
+    fn formats_watch_as_hex() {
+        let watch = MemoryWatch {
+            address: 5,
+            format: WatchFormat::Hex,
+        };
+
+        assert_eq!(
+            format_watch(&watch, peek_from(&[0, 0, 0, 0, 0, 0xAB])),
+            "$AB"
+        );
+    }
+
+    #[test]
+    fn formats_watch_as_decimal() {
+        let watch = MemoryWatch {
+            address: 0,
+            format: WatchFormat::Decimal,
+        };
+
+        assert_eq!(format_watch(&watch, peek_from(&[171])), "171");
+    }
+
+    #[test]
+    fn formats_watch_as_signed() {
+        let watch = MemoryWatch {
+            address: 0,
+            format: WatchFormat::Signed,
+        };
+
+        assert_eq!(format_watch(&watch, peek_from(&[0xFF])), "-1");
+    }
+}
@@ -1,31 +1,297 @@
-use std::{fs, path::PathBuf};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    io::{Cursor, Read as _, Write as _},
+    path::{Path, PathBuf},
+};
+
+mod config;
+mod debugger;
 
+use cartridge::mapper::{Cheat, CheatList};
+use config::{Config, NesButton};
 use directories::ProjectDirs;
 use dynwave::AudioPlayer;
 use egui_winit::winit::platform::x11::EventLoopBuilderExtX11 as _;
+use gilrs::{EventType, GamepadId, Gilrs};
 use plastic_core::{
     nes::NES,
     nes_audio::SAMPLE_RATE,
-    nes_controller::StandardNESKey,
     nes_display::{TV_HEIGHT, TV_WIDTH},
 };
 
 const MIN_STATE_SLOT: u8 = 0;
 const MAX_STATE_SLOT: u8 = 9;
 
-fn base_save_state_folder() -> Option<PathBuf> {
-    if let Some(proj_dirs) = ProjectDirs::from("Amjad50", "Plastic", "Plastic") {
-        let base_saved_states_dir = proj_dirs.data_local_dir().join("saved_states");
-        // Linux:   /home/../.local/share/plastic/saved_states
-        // Windows: C:\Users\..\AppData\Local\Plastic\Plastic\data\saved_states
-        // macOS:   /Users/../Library/Application Support/Amjad50.Plastic.Plastic/saved_states
+// capture a snapshot every couple of frames so rewinding doesn't eat all the CPU/RAM
+const REWIND_SNAPSHOT_INTERVAL: u64 = 2;
+// how many seconds of history the rewind buffer choices offer in the menu
+const REWIND_SECONDS_CHOICES: [u64; 4] = [5, 10, 20, 30];
+// assumes ~60 FPS, which is what the emulation core targets
+const ASSUMED_FPS: u64 = 60;
+
+const MIN_SPEED: f32 = 0.25;
+const MAX_SPEED: f32 = 4.0;
+const SPEED_STEP: f32 = 0.25;
+// multiplier applied while the hold-to-fast-forward key is held, regardless
+// of the configured default speed
+const FAST_FORWARD_SPEED: f32 = 4.0;
+
+enum MovieState {
+    Idle,
+    Recording { snapshot: Vec<u8>, inputs: Vec<u16> },
+    Playing { inputs: Vec<u16>, index: usize },
+}
+
+// which control is currently waiting for the next keypress/gamepad press
+// during a "Settings" menu rebind
+enum RebindCapture {
+    Keyboard(NesButton),
+    Gamepad(NesButton),
+}
+
+// shared base directory for all persisted app data (config, cheats, save
+// states); callers join their own file/subdir name onto this
+pub(crate) fn plastic_data_dir() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("Amjad50", "Plastic", "Plastic")?;
+    let dir = proj_dirs.data_local_dir().to_path_buf();
+    fs::create_dir_all(&dir).ok()?;
+
+    Some(dir)
+}
+
+fn base_cheats_file() -> Option<PathBuf> {
+    Some(plastic_data_dir()?.join("cheats.txt"))
+}
+
+fn load_cheat_codes() -> Vec<String> {
+    base_cheats_file()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|contents| contents.lines().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+fn save_cheat_codes(codes: &[String]) {
+    if let Some(path) = base_cheats_file() {
+        _ = fs::write(path, codes.join("\n"));
+    }
+}
+
+fn save_movie_file(path: &Path, snapshot: &[u8], inputs: &[u16]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+
+    file.write_all(&(snapshot.len() as u64).to_le_bytes())?;
+    file.write_all(snapshot)?;
+
+    file.write_all(&(inputs.len() as u64).to_le_bytes())?;
+    for frame in inputs {
+        file.write_all(&frame.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn truncated_movie_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "truncated movie file")
+}
+
+fn read_u64_at(data: &[u8], offset: usize) -> std::io::Result<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(truncated_movie_error)
+}
+
+fn parse_movie_bytes(data: &[u8]) -> std::io::Result<(Vec<u8>, Vec<u16>)> {
+    let mut offset = 0;
+
+    let snapshot_len = read_u64_at(data, offset)? as usize;
+    offset += 8;
+    let snapshot = data
+        .get(offset..offset + snapshot_len)
+        .ok_or_else(truncated_movie_error)?
+        .to_vec();
+    offset += snapshot_len;
+
+    let frame_count = read_u64_at(data, offset)? as usize;
+    offset += 8;
+    let mut inputs = Vec::with_capacity(frame_count);
+    for i in 0..frame_count {
+        let start = offset + i * 2;
+        let bytes = data
+            .get(start..start + 2)
+            .ok_or_else(truncated_movie_error)?;
+        inputs.push(u16::from_le_bytes(bytes.try_into().unwrap()));
+    }
+
+    Ok((snapshot, inputs))
+}
+
+fn load_movie_file(path: &Path) -> std::io::Result<(Vec<u8>, Vec<u16>)> {
+    parse_movie_bytes(&fs::read(path)?)
+}
 
-        fs::create_dir_all(&base_saved_states_dir).ok()?;
+#[cfg(test)]
+mod movie_file_tests {
+    use super::*;
 
-        Some(base_saved_states_dir)
-    } else {
-        None
+    #[test]
+    fn rejects_empty_buffer() {
+        assert!(parse_movie_bytes(&[]).is_err());
     }
+
+    #[test]
+    fn rejects_truncated_snapshot() {
+        // claims an 8-byte snapshot but only provides 2
+        let mut data = 8u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&[1, 2]);
+        assert!(parse_movie_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_frame_count_header() {
+        // snapshot_len = 0, then only 3 bytes remain instead of an 8-byte count
+        let mut data = 0u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert!(parse_movie_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_frame_data() {
+        // snapshot_len = 0, frame_count = 2, but only one u16 of input data
+        let mut data = 0u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        assert!(parse_movie_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn parses_well_formed_buffer() {
+        let mut data = 3u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&[9, 9, 9]);
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&0x1234u16.to_le_bytes());
+        data.extend_from_slice(&0x5678u16.to_le_bytes());
+
+        let (snapshot, inputs) = parse_movie_bytes(&data).unwrap();
+        assert_eq!(snapshot, vec![9, 9, 9]);
+        assert_eq!(inputs, vec![0x1234, 0x5678]);
+    }
+}
+
+fn is_archive_extension(path: &Path) -> bool {
+    matches!(
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref(),
+        Some("zip") | Some("7z") | Some("gz")
+    )
+}
+
+fn extract_nes_from_zip(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let Ok(mut archive) = zip::ZipArchive::new(file) else {
+        return Vec::new();
+    };
+
+    (0..archive.len())
+        .filter_map(|i| archive.by_index(i).ok())
+        .filter(|entry| entry.name().to_ascii_lowercase().ends_with(".nes"))
+        .filter_map(|mut entry| {
+            let name = entry.name().to_string();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).ok()?;
+            Some((name, bytes))
+        })
+        .collect()
+}
+
+fn extract_nes_from_gz(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+
+    let mut bytes = Vec::new();
+    if flate2::read::GzDecoder::new(file)
+        .read_to_end(&mut bytes)
+        .is_err()
+    {
+        return Vec::new();
+    }
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "rom.nes".to_owned());
+
+    vec![(name, bytes)]
+}
+
+fn extract_nes_from_7z(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let Ok(temp_dir) = tempfile::tempdir() else {
+        return Vec::new();
+    };
+    if sevenz_rust::decompress_file(path, temp_dir.path()).is_err() {
+        return Vec::new();
+    }
+
+    let Ok(read_dir) = fs::read_dir(temp_dir.path()) else {
+        return Vec::new();
+    };
+
+    read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|entry_path| {
+            entry_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("nes"))
+        })
+        .filter_map(|entry_path| {
+            let bytes = fs::read(&entry_path).ok()?;
+            let name = entry_path.file_name()?.to_string_lossy().into_owned();
+            Some((name, bytes))
+        })
+        .collect()
+}
+
+/// Enumerates the `.nes` entries found inside a `.zip`/`.7z`/`.gz` archive.
+/// For `.7z`, only entries at the archive's top level are found.
+fn extract_nes_entries(path: &Path) -> Vec<(String, Vec<u8>)> {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("zip") => extract_nes_from_zip(path),
+        Some("7z") => extract_nes_from_7z(path),
+        Some("gz") => extract_nes_from_gz(path),
+        _ => Vec::new(),
+    }
+}
+
+// `NES` only exposes a path-based constructor, so a ROM extracted from an
+// archive is spilled to a temp file and loaded through that instead of
+// requiring a byte-based constructor on `NES` itself.
+fn nes_from_bytes(bytes: &[u8]) -> Option<NES> {
+    let mut temp = tempfile::NamedTempFile::new().ok()?;
+    temp.write_all(bytes).ok()?;
+    NES::new(temp.path()).ok()
+}
+
+fn base_save_state_folder() -> Option<PathBuf> {
+    // Linux:   /home/../.local/share/plastic/saved_states
+    // Windows: C:\Users\..\AppData\Local\Plastic\Plastic\data\saved_states
+    // macOS:   /Users/../Library/Application Support/Amjad50.Plastic.Plastic/saved_states
+    let base_saved_states_dir = plastic_data_dir()?.join("saved_states");
+    fs::create_dir_all(&base_saved_states_dir).ok()?;
+
+    Some(base_saved_states_dir)
 }
 
 struct App {
@@ -34,16 +300,85 @@ struct App {
     image_texture: egui::TextureHandle,
     paused: bool,
     last_frame_time: std::time::Instant,
+    rewind_buffer: VecDeque<Vec<u8>>,
+    rewind_buffer_seconds: u64,
+    rewinding: bool,
+    frame_counter: u64,
+    cheats: CheatList,
+    cheat_codes: Vec<String>,
+    cheat_code_input: String,
+    cheat_error: Option<String>,
+    movie_state: MovieState,
+    // the live keyboard/gamepad button state as of the last handle_input()
+    // call, sampled into a movie recording once per emulated frame
+    current_input_bits: u16,
+    gilrs: Gilrs,
+    // per-device button state, so one gamepad's release/disconnect can't
+    // clobber another gamepad's held buttons
+    gamepad_buttons: HashMap<GamepadId, u16>,
+    archive_candidates: Option<Vec<(String, Vec<u8>)>>,
+    config: Config,
+    key_bindings: Vec<(NesButton, egui::Key)>,
+    gamepad_bindings: Vec<(NesButton, gilrs::Button)>,
+    rebind_capture: Option<RebindCapture>,
+    speed: f32,
+    fast_forwarding: bool,
+    slow_motion_accumulator: f32,
+    debug_window_open: bool,
+    memory_watches: Vec<debugger::MemoryWatch>,
+    new_watch_address_input: String,
+    new_watch_format: debugger::WatchFormat,
+}
+
+fn cheats_from_codes(codes: &[String]) -> CheatList {
+    let mut cheats = CheatList::new();
+    for code in codes {
+        if let Ok(cheat) = Cheat::from_game_genie_code(code) {
+            cheats.add(cheat);
+        }
+    }
+    cheats
 }
 
 impl App {
-    pub fn new(ctx: &egui::Context, nes: NES) -> Self {
+    pub fn new(ctx: &egui::Context, nes: NES, config: Config) -> Self {
+        let cheat_codes = load_cheat_codes();
+        let cheats = cheats_from_codes(&cheat_codes);
+
+        let key_bindings = config::resolve_key_bindings(&config);
+        let gamepad_bindings = config::resolve_gamepad_bindings(&config);
+        let speed = config.default_speed.clamp(MIN_SPEED, MAX_SPEED);
+
         Self {
             nes,
             audio_player: AudioPlayer::new(SAMPLE_RATE, dynwave::BufferSize::QuarterSecond)
                 .unwrap(),
             paused: false,
             last_frame_time: std::time::Instant::now(),
+            rewind_buffer: VecDeque::new(),
+            rewind_buffer_seconds: config.rewind_buffer_seconds,
+            rewinding: false,
+            frame_counter: 0,
+            cheats,
+            cheat_codes,
+            cheat_code_input: String::new(),
+            cheat_error: None,
+            movie_state: MovieState::Idle,
+            current_input_bits: 0,
+            gilrs: Gilrs::new().unwrap(),
+            gamepad_buttons: HashMap::new(),
+            archive_candidates: None,
+            config,
+            key_bindings,
+            gamepad_bindings,
+            rebind_capture: None,
+            speed,
+            fast_forwarding: false,
+            slow_motion_accumulator: 0.0,
+            debug_window_open: false,
+            memory_watches: Vec::new(),
+            new_watch_address_input: String::new(),
+            new_watch_format: debugger::WatchFormat::Hex,
             image_texture: ctx.load_texture(
                 "nes-image",
                 egui::ColorImage::from_rgba_unmultiplied(
@@ -105,52 +440,407 @@ impl App {
         self.nes.load_state(&file).unwrap();
     }
 
+    fn add_cheat_code(&mut self, code: String) {
+        match Cheat::from_game_genie_code(&code) {
+            Ok(cheat) => {
+                self.cheats.add(cheat);
+                self.cheat_codes.push(code);
+                save_cheat_codes(&self.cheat_codes);
+                self.cheat_error = None;
+            }
+            Err(_) => {
+                self.cheat_error = Some(format!("Invalid Game Genie code: {code}"));
+            }
+        }
+    }
+
+    fn remove_cheat_code(&mut self, index: usize) {
+        self.cheat_codes.remove(index);
+        // Rebuilt from the remaining codes rather than `CheatList::remove`d
+        // by address, since two codes can share an address and removing one
+        // must not also drop the other's still-active cheat.
+        self.cheats = cheats_from_codes(&self.cheat_codes);
+        save_cheat_codes(&self.cheat_codes);
+    }
+
+    fn load_rom_path(&mut self, path: &Path) {
+        if !is_archive_extension(path) {
+            self.nes = NES::new(path).unwrap();
+            return;
+        }
+
+        let mut entries = extract_nes_entries(path);
+        match entries.len() {
+            0 => println!("[ERROR] Archive does not contain a .nes ROM"),
+            1 => {
+                let (_, bytes) = entries.remove(0);
+                match nes_from_bytes(&bytes) {
+                    Some(nes) => self.nes = nes,
+                    None => println!("[ERROR] Failed to load ROM extracted from archive"),
+                }
+            }
+            _ => self.archive_candidates = Some(entries),
+        }
+    }
+
+    fn show_archive_picker(&mut self, ctx: &egui::Context) {
+        let Some(candidates) = self.archive_candidates.take() else {
+            return;
+        };
+
+        let mut chosen_index = None;
+        let mut cancelled = false;
+
+        egui::Window::new("Select ROM").show(ctx, |ui| {
+            for (i, (name, _)) in candidates.iter().enumerate() {
+                if ui.button(name).clicked() {
+                    chosen_index = Some(i);
+                }
+            }
+            if ui.button("Cancel").clicked() {
+                cancelled = true;
+            }
+        });
+
+        if let Some(i) = chosen_index {
+            let (_, bytes) = &candidates[i];
+            match nes_from_bytes(bytes) {
+                Some(nes) => self.nes = nes,
+                None => println!("[ERROR] Failed to load ROM extracted from archive"),
+            }
+        } else if !cancelled {
+            self.archive_candidates = Some(candidates);
+        }
+    }
+
+    // Registers, disassembly, and live memory watches all need read-only
+    // accessors (register state, CPU/PPU peek) that `NES` doesn't expose
+    // today, so this window is scoped down to what it can actually drive:
+    // stepping by frame, and tracking which addresses a user wants watched
+    // once that peek support exists. `debugger::disassemble`/`format_watch`
+    // stay in `debugger.rs`, tested against plain closures, ready to wire in
+    // behind real accessors without any change to their own logic.
+    fn show_debugger_window(&mut self, ctx: &egui::Context) {
+        if !self.debug_window_open || self.nes.is_empty() {
+            return;
+        }
+
+        let mut open = self.debug_window_open;
+
+        egui::Window::new("Debugger")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(
+                    "Live registers, disassembly, and watch values need CPU/PPU \
+                     peek accessors that NES doesn't expose yet; this window only \
+                     offers what's possible without them.",
+                );
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.paused, egui::Button::new("Step Frame"))
+                        .clicked()
+                    {
+                        _ = self.step_emulation_frame();
+                    }
+                });
+
+                ui.separator();
+                ui.label("Memory Watches (addresses only, until peek support lands)");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.new_watch_address_input);
+                    egui::ComboBox::from_label("Format")
+                        .selected_text(debugger::watch_format_label(self.new_watch_format))
+                        .show_ui(ui, |ui| {
+                            for format in debugger::WATCH_FORMATS {
+                                ui.selectable_value(
+                                    &mut self.new_watch_format,
+                                    format,
+                                    debugger::watch_format_label(format),
+                                );
+                            }
+                        });
+                    if ui.button("Add Watch").clicked() {
+                        if let Ok(address) = u16::from_str_radix(
+                            self.new_watch_address_input.trim_start_matches('$'),
+                            16,
+                        ) {
+                            self.memory_watches.push(debugger::MemoryWatch {
+                                address,
+                                format: self.new_watch_format,
+                            });
+                            self.new_watch_address_input.clear();
+                        }
+                    }
+                });
+
+                let mut to_remove = None;
+                for (i, watch) in self.memory_watches.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.monospace(format!(
+                            "${:04X} ({})",
+                            watch.address,
+                            debugger::watch_format_label(watch.format)
+                        ));
+                        if ui.button("Remove").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    self.memory_watches.remove(i);
+                }
+            });
+
+        self.debug_window_open = open;
+    }
+
+    fn start_recording(&mut self) {
+        if self.nes.is_empty() {
+            return;
+        }
+
+        let mut cursor = Cursor::new(Vec::new());
+        if self.nes.save_state(&mut cursor).is_ok() {
+            self.movie_state = MovieState::Recording {
+                snapshot: cursor.into_inner(),
+                inputs: Vec::new(),
+            };
+        }
+    }
+
+    fn stop_recording(&mut self) {
+        let MovieState::Recording { snapshot, inputs } =
+            std::mem::replace(&mut self.movie_state, MovieState::Idle)
+        else {
+            return;
+        };
+
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Plastic Movie", &["movie"])
+            .save_file()
+        {
+            _ = save_movie_file(&path, &snapshot, &inputs);
+        }
+    }
+
+    fn play_movie(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Plastic Movie", &["movie"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        match load_movie_file(&path) {
+            Ok((snapshot, inputs)) => {
+                let mut cursor = Cursor::new(snapshot);
+                if self.nes.load_state(&mut cursor).is_ok() {
+                    self.movie_state = MovieState::Playing { inputs, index: 0 };
+                }
+            }
+            Err(err) => println!("[ERROR] Failed to load movie file: {err}"),
+        }
+    }
+
+    // clocks one emulated frame, capturing a rewind snapshot if due and
+    // advancing any in-progress movie by exactly one sample, then returns
+    // the audio it produced. Movie input is sampled/replayed here rather
+    // than in handle_input so recording and playback stay frame-accurate
+    // regardless of the fast-forward/slow-motion speed multiplier -- at
+    // 4x speed this runs 4 times per handle_input() call, and at <1x speed
+    // it may not run at all that update().
+    fn step_emulation_frame(&mut self) -> Vec<f32> {
+        match &mut self.movie_state {
+            MovieState::Playing { inputs, index } => {
+                if let Some(&bits) = inputs.get(*index) {
+                    *index += 1;
+                    for (nes_button, _) in &self.key_bindings {
+                        let down = bits & nes_button.bit() != 0;
+                        self.nes
+                            .controller()
+                            .set_state(nes_button.to_standard_key(), down);
+                    }
+                } else {
+                    self.movie_state = MovieState::Idle;
+                }
+            }
+            MovieState::Recording { inputs, .. } => {
+                inputs.push(self.current_input_bits);
+            }
+            MovieState::Idle => {}
+        }
+
+        self.nes.clock_for_frame();
+
+        self.frame_counter += 1;
+        if self.frame_counter % REWIND_SNAPSHOT_INTERVAL == 0 {
+            self.capture_rewind_snapshot();
+        }
+
+        self.nes.audio_buffer()
+    }
+
+    fn rewind_capacity(&self) -> usize {
+        (self.rewind_buffer_seconds * ASSUMED_FPS / REWIND_SNAPSHOT_INTERVAL) as usize
+    }
+
+    fn capture_rewind_snapshot(&mut self) {
+        if self.nes.is_empty() {
+            return;
+        }
+
+        let mut cursor = Cursor::new(Vec::new());
+        if self.nes.save_state(&mut cursor).is_ok() {
+            self.rewind_buffer.push_back(cursor.into_inner());
+
+            while self.rewind_buffer.len() > self.rewind_capacity() {
+                self.rewind_buffer.pop_front();
+            }
+        }
+    }
+
+    fn step_rewind(&mut self) {
+        if let Some(snapshot) = self.rewind_buffer.pop_back() {
+            let mut cursor = Cursor::new(snapshot);
+            _ = self.nes.load_state(&mut cursor);
+        } else {
+            // nothing left to rewind to, resume playing forward
+            self.rewinding = false;
+        }
+    }
+
+    fn gamepad_button_bit(&self, button: gilrs::Button) -> Option<u16> {
+        self.gamepad_bindings
+            .iter()
+            .find(|(_, bound)| *bound == button)
+            .map(|(nes_button, _)| nes_button.bit())
+    }
+
+    fn poll_gamepads(&mut self) {
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(RebindCapture::Gamepad(nes_button)) = self.rebind_capture.take() {
+                        self.rebind_gamepad(nes_button, button);
+                    } else if let Some(bit) = self.gamepad_button_bit(button) {
+                        *self.gamepad_buttons.entry(event.id).or_insert(0) |= bit;
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(bit) = self.gamepad_button_bit(button) {
+                        if let Some(state) = self.gamepad_buttons.get_mut(&event.id) {
+                            *state &= !bit;
+                        }
+                    }
+                }
+                // stale button state from a gamepad that just disappeared would
+                // otherwise get stuck "held" forever
+                EventType::Disconnected => {
+                    self.gamepad_buttons.remove(&event.id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // combined state across every connected gamepad. plastic_core only
+    // exposes a single `controller()` accessor today, so every pad is
+    // routed to NES port 1 rather than to its own port -- routing a second
+    // pad to port 2 needs a corresponding accessor added there first.
+    fn combined_gamepad_buttons(&self) -> u16 {
+        self.gamepad_buttons
+            .values()
+            .fold(0, |acc, &bits| acc | bits)
+    }
+
+    fn rebind_key(&mut self, nes_button: NesButton, key: egui::Key) {
+        self.config
+            .key_bindings
+            .insert(nes_button.name().to_owned(), key.name().to_owned());
+        config::save(&self.config);
+        self.key_bindings = config::resolve_key_bindings(&self.config);
+    }
+
+    fn rebind_gamepad(&mut self, nes_button: NesButton, button: gilrs::Button) {
+        self.config
+            .gamepad_bindings
+            .insert(nes_button.name().to_owned(), format!("{button:?}"));
+        config::save(&self.config);
+        self.gamepad_bindings = config::resolve_gamepad_bindings(&self.config);
+    }
+
     fn handle_input(&mut self, ctx: &egui::Context) {
         ctx.input(|i| {
             if !i.raw.dropped_files.is_empty() {
-                let file = i
-                    .raw
-                    .dropped_files
-                    .iter()
-                    .filter_map(|f| f.path.as_ref()).find(|f| f.extension().map(|e| e == "nes").unwrap_or(false));
+                let file = i.raw.dropped_files.iter().filter_map(|f| f.path.as_ref()).find(|f| {
+                    f.extension()
+                        .map(|e| e == "nes" || is_archive_extension(f))
+                        .unwrap_or(false)
+                });
 
                 if let Some(file) = file {
-                    self.nes = NES::new(file).unwrap();
+                    let file = file.clone();
+                    self.load_rom_path(&file);
                 } else {
                     // convert to error alert
-                    println!("[ERROR] Dropped file is not a NES ROM, must have .nes extension");
+                    println!(
+                        "[ERROR] Dropped file is not a NES ROM, must have .nes/.zip/.7z/.gz extension"
+                    );
                 }
             }
             if !i.focused {
                 return;
             }
 
-            if !self.nes.is_empty() {
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::B, i.key_down(egui::Key::J));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::A, i.key_down(egui::Key::K));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::Select, i.key_down(egui::Key::U));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::Start, i.key_down(egui::Key::I));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::Up, i.key_down(egui::Key::W));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::Down, i.key_down(egui::Key::S));
-                self.nes
-                    .controller()
-                    .set_state(StandardNESKey::Left, i.key_down(egui::Key::A));
+            match self.rebind_capture {
+                Some(RebindCapture::Keyboard(nes_button)) => {
+                    if let Some(key) = i.keys_down.iter().next().copied() {
+                        self.rebind_capture = None;
+                        if key != egui::Key::Escape {
+                            self.rebind_key(nes_button, key);
+                        }
+                    }
+                    return;
+                }
+                Some(RebindCapture::Gamepad(_)) => {
+                    // the actual button press is consumed in poll_gamepads()
+                    if i.key_down(egui::Key::Escape) {
+                        self.rebind_capture = None;
+                    }
+                    return;
+                }
+                None => {}
+            }
+
+            if self.nes.is_empty() {
+                return;
+            }
+
+            self.rewinding = i.key_down(egui::Key::Backspace) && !self.rewind_buffer.is_empty();
+            self.fast_forwarding = i.key_down(egui::Key::Tab);
+
+            if matches!(self.movie_state, MovieState::Playing { .. }) {
+                // controller state during playback comes from the recorded
+                // input stream, applied once per emulated frame in
+                // step_emulation_frame rather than once per update()
+                return;
+            }
+
+            let mut bits = 0u16;
+            for &(nes_button, egui_key) in &self.key_bindings {
+                let bit = nes_button.bit();
+                let down = i.key_down(egui_key) || (self.combined_gamepad_buttons() & bit != 0);
                 self.nes
                     .controller()
-                    .set_state(StandardNESKey::Right, i.key_down(egui::Key::D));
+                    .set_state(nes_button.to_standard_key(), down);
+                if down {
+                    bits |= bit;
+                }
             }
+
+            // recorded in step_emulation_frame, once per emulated frame
+            self.current_input_bits = bits;
         });
     }
 
@@ -158,21 +848,39 @@ impl App {
         let fps = 1.0 / self.last_frame_time.elapsed().as_secs_f64();
         self.last_frame_time = std::time::Instant::now();
         let title = format!(
-            "Plastic ({:.0} FPS) {}",
+            "Plastic ({:.0} FPS) ({:.2}x) {}",
             fps,
+            self.effective_speed(),
             if self.paused { "- Paused" } else { "" }
         );
 
         ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
     }
 
+    fn effective_speed(&self) -> f32 {
+        if self.fast_forwarding {
+            FAST_FORWARD_SPEED
+        } else {
+            self.speed
+        }
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self.config.default_speed = self.speed;
+        config::save(&self.config);
+    }
+
     fn show_menu(&mut self, ui: &mut egui::Ui) {
         egui::menu::bar(ui, |ui| {
             ui.menu_button("File", |ui| {
                 if ui.button("Open").clicked() {
                     if let Some(file) = rfd::FileDialog::new()
-                        .add_filter("NES ROM", &["nes"])
-                        .pick_file() { self.nes = NES::new(file).unwrap(); }
+                        .add_filter("NES ROM / Archive", &["nes", "zip", "7z", "gz"])
+                        .pick_file()
+                    {
+                        self.load_rom_path(&file);
+                    }
                 }
                 if ui.button("Reset").clicked() {
                     self.nes.reset();
@@ -224,29 +932,197 @@ impl App {
                     }
                 }
             });
+            ui.menu_button("Movie", |ui| {
+                let recording = matches!(self.movie_state, MovieState::Recording { .. });
+                let playing = matches!(self.movie_state, MovieState::Playing { .. });
+
+                if ui
+                    .add_enabled(!recording && !playing, egui::Button::new("Start Recording"))
+                    .clicked()
+                {
+                    self.start_recording();
+                }
+                if ui
+                    .add_enabled(recording, egui::Button::new("Stop"))
+                    .clicked()
+                {
+                    self.stop_recording();
+                }
+                if ui
+                    .add_enabled(!recording && !playing, egui::Button::new("Play Movie"))
+                    .clicked()
+                {
+                    self.play_movie();
+                }
+            });
+            ui.menu_button("Cheats", |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.cheat_code_input);
+                    if ui.button("Add").clicked() && !self.cheat_code_input.is_empty() {
+                        let code = std::mem::take(&mut self.cheat_code_input);
+                        self.add_cheat_code(code);
+                    }
+                });
+
+                if let Some(error) = &self.cheat_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                let mut to_remove = None;
+                for (i, code) in self.cheat_codes.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(code);
+                        if ui.button("Remove").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    self.remove_cheat_code(i);
+                }
+            });
+            ui.menu_button("Rewind", |ui| {
+                ui.label("Buffer length");
+                for seconds in REWIND_SECONDS_CHOICES {
+                    if ui
+                        .radio_value(
+                            &mut self.rewind_buffer_seconds,
+                            seconds,
+                            format!("{seconds}s"),
+                        )
+                        .clicked()
+                    {
+                        self.config.rewind_buffer_seconds = seconds;
+                        config::save(&self.config);
+
+                        let capacity = self.rewind_capacity();
+                        while self.rewind_buffer.len() > capacity {
+                            self.rewind_buffer.pop_front();
+                        }
+                    }
+                }
+            });
+            ui.menu_button("Debug", |ui| {
+                ui.checkbox(&mut self.debug_window_open, "Show Debugger");
+            });
+            ui.menu_button("Speed", |ui| {
+                ui.label(format!("Default speed: {:.2}x", self.speed));
+                ui.horizontal(|ui| {
+                    if ui.button("-").clicked() {
+                        self.set_speed(self.speed - SPEED_STEP);
+                    }
+                    if ui.button("Reset").clicked() {
+                        self.set_speed(1.0);
+                    }
+                    if ui.button("+").clicked() {
+                        self.set_speed(self.speed + SPEED_STEP);
+                    }
+                });
+                ui.label("Hold Tab to fast-forward");
+            });
+            ui.menu_button("Settings", |ui| {
+                if let Some(RebindCapture::Keyboard(nes_button)) = self.rebind_capture {
+                    ui.label(format!(
+                        "Press any key for {}... (Esc to cancel)",
+                        nes_button.name()
+                    ));
+                } else if let Some(RebindCapture::Gamepad(nes_button)) = self.rebind_capture {
+                    ui.label(format!(
+                        "Press a gamepad button for {}... (Esc to cancel)",
+                        nes_button.name()
+                    ));
+                } else {
+                    egui::Grid::new("control_bindings").show(ui, |ui| {
+                        for nes_button in NesButton::ALL {
+                            let key = self
+                                .key_bindings
+                                .iter()
+                                .find(|(b, _)| *b == nes_button)
+                                .map(|(_, k)| k.name())
+                                .unwrap_or("?");
+                            let gamepad_button = self
+                                .gamepad_bindings
+                                .iter()
+                                .find(|(b, _)| *b == nes_button)
+                                .map(|(_, b)| format!("{b:?}"))
+                                .unwrap_or_else(|| "?".to_owned());
+
+                            ui.label(nes_button.name());
+                            if ui.button(key).clicked() {
+                                self.rebind_capture = Some(RebindCapture::Keyboard(nes_button));
+                            }
+                            if ui.button(gamepad_button).clicked() {
+                                self.rebind_capture = Some(RebindCapture::Gamepad(nes_button));
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.separator();
+
+                    let mut volume = self.config.audio_volume;
+                    if ui
+                        .add(egui::Slider::new(&mut volume, 0.0..=1.0).text("Volume"))
+                        .changed()
+                    {
+                        self.config.audio_volume = volume;
+                        config::save(&self.config);
+                    }
+
+                    ui.separator();
+                    ui.label(
+                        "Note: NES only exposes a single controller() port, so every \
+                         connected gamepad currently drives Player 1 — 2-player input \
+                         isn't possible yet.",
+                    );
+                }
+            });
         });
     }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if !self.paused && !self.nes.is_empty() {
-            self.nes.clock_for_frame();
-            let audio_buffer = self.nes.audio_buffer();
-            // convert from 1 channel to 2 channels
-            self.audio_player.queue(
-                &audio_buffer
-                    .iter()
-                    .flat_map(|&s| [s, s])
-                    .collect::<Vec<_>>(),
-            );
-            self.audio_player.play().unwrap();
+        if self.rewinding && !self.nes.is_empty() {
+            self.step_rewind();
+            self.audio_player.pause().unwrap();
+        } else if !self.paused && !self.nes.is_empty() {
+            let speed = self.effective_speed();
+            let mut audio_buffer = None;
+
+            // fractional frames carry over between updates, so multipliers
+            // like 0.25x or 1.25x average out to the displayed speed. Drop all
+            // but the last frame's audio to avoid overrunning the audio buffer
+            // while running faster than real-time
+            self.slow_motion_accumulator += speed;
+            while self.slow_motion_accumulator >= 1.0 {
+                self.slow_motion_accumulator -= 1.0;
+                audio_buffer = Some(self.step_emulation_frame());
+            }
+
+            if let Some(audio_buffer) = audio_buffer {
+                let volume = self.config.audio_volume;
+                // convert from 1 channel to 2 channels, applying the
+                // configured volume
+                self.audio_player.queue(
+                    &audio_buffer
+                        .iter()
+                        .flat_map(|&s| [s * volume, s * volume])
+                        .collect::<Vec<_>>(),
+                );
+                self.audio_player.play().unwrap();
+            } else {
+                self.audio_player.pause().unwrap();
+            }
         } else {
             self.audio_player.pause().unwrap();
         }
 
         self.update_title(ctx);
+        self.poll_gamepads();
         self.handle_input(ctx);
+        self.show_archive_picker(ctx);
+        self.show_debugger_window(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             self.show_menu(ui);
@@ -302,6 +1178,16 @@ impl eframe::App for App {
                             egui::Color32::from_black_alpha(200),
                         );
                     }
+
+                    if self.rewinding {
+                        ui.painter().text(
+                            rect.left_top() + egui::vec2(10.0, 10.0),
+                            egui::Align2::LEFT_TOP,
+                            "⏪ Rewinding",
+                            egui::FontId::proportional(20.0),
+                            egui::Color32::WHITE,
+                        );
+                    }
                 } else {
                     ui.label("No game loaded");
                 }
@@ -319,6 +1205,8 @@ pub fn main() -> Result<(), eframe::Error> {
         None => NES::new_without_file(),
     };
 
+    let config = config::load();
+
     eframe::run_native(
         "Plastic",
         eframe::NativeOptions {
@@ -328,6 +1216,6 @@ pub fn main() -> Result<(), eframe::Error> {
             window_builder: Some(Box::new(|builder| builder.with_drag_and_drop(true))),
             ..Default::default()
         },
-        Box::new(|c| Ok(Box::new(App::new(&c.egui_ctx, nes)))),
+        Box::new(|c| Ok(Box::new(App::new(&c.egui_ctx, nes, config)))),
     )
 }